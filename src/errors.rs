@@ -0,0 +1,12 @@
+/*
+    Central error type for the crate, built with error_chain so the various
+    I/O and process-management failures we hit (opening /proc/mounts,
+    spawning the check process, talking to Prometheus) can all be threaded
+    through `Result` with a bit of human-readable context via `chain_err`.
+*/
+
+error_chain! {
+    foreign_links {
+        Io(::std::io::Error);
+    }
+}
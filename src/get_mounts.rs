@@ -0,0 +1,42 @@
+/*
+    Helpers for enumerating the system's current mountpoints.
+
+    We parse /proc/mounts directly rather than wrapping getmntent(3), which
+    isn't thread-safe. check_mounts in main.rs calls this once per tick,
+    before its rayon-parallel check loop starts.
+*/
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+
+use errors::*;
+
+#[derive(Debug, Clone)]
+pub struct MountPoint {
+    pub path: PathBuf,
+    pub fstype: String,
+}
+
+pub fn get_mount_points() -> Result<Vec<MountPoint>> {
+    let file = File::open("/proc/mounts").chain_err(|| "Unable to open /proc/mounts")?;
+    let reader = BufReader::new(file);
+
+    let mut mount_points = Vec::new();
+    for line in reader.lines() {
+        let line = line.chain_err(|| "Unable to read line from /proc/mounts")?;
+
+        // Each line is: device mountpoint fstype options freq passno
+        let mut fields = line.split_whitespace();
+        fields.next(); // device, unused
+        let path = match fields.next() {
+            Some(path) => PathBuf::from(path),
+            None => continue,
+        };
+        let fstype = fields.next().unwrap_or("unknown").to_owned();
+
+        mount_points.push(MountPoint { path, fstype });
+    }
+
+    Ok(mount_points)
+}
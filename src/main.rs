@@ -41,9 +41,10 @@ extern crate lazy_static;
 #[macro_use]
 extern crate prometheus;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::process;
 use std::str;
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use std::path::{Path, PathBuf};
@@ -57,6 +58,12 @@ use rayon::prelude::*;
 mod errors;
 mod get_mounts;
 
+#[cfg(target_os = "linux")]
+mod reactor;
+
+#[cfg(target_os = "linux")]
+use reactor::StallReactor;
+
 use errors::*;
 
 fn handle_syslog_error(err: std::io::Error) -> usize {
@@ -65,6 +72,15 @@ fn handle_syslog_error(err: std::io::Error) -> usize {
     0
 }
 
+/// Mirrors systemd's MOUNTING -> ..._SIGTERM -> ..._SIGKILL escalation: a
+/// stalled check is signaled gently first, and only killed outright if it
+/// hasn't reaped by the end of its grace period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KillEscalation {
+    Sigtermed,
+    Sigkilled,
+}
+
 #[derive(Debug)]
 enum MountStatus {
     Alive,
@@ -73,6 +89,13 @@ enum MountStatus {
     CheckRunning {
         process: process::Child,
         start_time: Instant,
+        escalation: KillEscalation,
+        signaled_at: Instant,
+        // Poll cycles this check has survived since being SIGKILL'd; stays
+        // 0 until the escalation is Sigkilled. Bounds the retry budget in
+        // check_mounts so a truly unkillable process eventually gets
+        // marked wedged instead of retried forever.
+        stalled_cycles: u32,
     }
 }
 
@@ -86,10 +109,72 @@ impl MountStatus {
     }
 }
 
+/// A debounced view of a mount's health that only flips to `Dead` once a
+/// failure has proven persistent, and eases back to `Healthy` through
+/// `Recovering` rather than snapping straight back: a single transient
+/// timeout that immediately recovers shouldn't read the same as a mount
+/// that's been dead for an hour. Mirrors the "indeterminate vs. confirmed"
+/// idea from systemd's mount unit state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HealthState {
+    Healthy,
+    Degraded,
+    Dead,
+    Recovering,
+}
+
+impl HealthState {
+    /// Numeric ordinal used for the Prometheus gauge; higher is worse.
+    fn as_metric_value(&self) -> f64 {
+        match *self {
+            HealthState::Healthy => 0.0,
+            HealthState::Recovering => 1.0,
+            HealthState::Degraded => 2.0,
+            HealthState::Dead => 3.0,
+        }
+    }
+
+    /// Determines the next state given whether the latest check passed,
+    /// the current run length of consecutive failures, and how many of
+    /// those are required before a mount is declared outright dead.
+    fn advance(self, check_passed: bool, consecutive_failures: u32, dead_after: u32) -> HealthState {
+        if check_passed {
+            match self {
+                HealthState::Degraded | HealthState::Dead => HealthState::Recovering,
+                HealthState::Recovering | HealthState::Healthy => HealthState::Healthy,
+            }
+        } else if consecutive_failures >= dead_after {
+            HealthState::Dead
+        } else {
+            HealthState::Degraded
+        }
+    }
+}
+
+/// A mount's check status together with the filesystem type it was last
+/// seen mounted as, so Prometheus metrics can be labeled by both, and the
+/// bookkeeping behind the flap-aware HealthState above.
+#[derive(Debug)]
+struct MountRecord {
+    fstype: String,
+    status: MountStatus,
+    consecutive_failures: u32,
+    health: HealthState,
+    health_changed_at: Instant,
+    // Set once a CheckRunning process outlives its stalled-cycle retry
+    // budget; suppresses further kill attempts and new check spawns for
+    // this mountpoint until the zombie finally reaps.
+    wedged: bool,
+}
+
 quick_main!{ real_main }
 
 fn real_main() -> Result<()> {
     let mut poll_interval = 60;
+    let mut kill_grace_period = 5;
+    let mut force_unmount_after: Option<u32> = None;
+    let mut dead_after = 3;
+    let mut max_stalled_cycles = 10;
     let mut prometheus_push_gateway: Option<String> = None;
 
     {
@@ -121,44 +206,109 @@ fn real_main() -> Result<()> {
             "Number of seconds to wait before checking mounts",
         );
 
+        ap.refer(&mut kill_grace_period).add_option(
+            &["--kill-grace-period"],
+            Store,
+            "Number of seconds to wait after SIGTERM-ing a stalled check before escalating to SIGKILL",
+        );
+
+        ap.refer(&mut force_unmount_after).add_option(
+            &["--force-unmount-after"],
+            StoreOption,
+            "Attempt a forced unmount (umount2 with MNT_FORCE) after this many consecutive \
+             failed checks on a mountpoint; disabled by default",
+        );
+
+        ap.refer(&mut dead_after).add_option(
+            &["--dead-after"],
+            Store,
+            "Number of consecutive failed checks before a mount is declared Dead rather than \
+             merely Degraded",
+        );
+
+        ap.refer(&mut max_stalled_cycles).add_option(
+            &["--max-stalled-cycles"],
+            Store,
+            "Number of poll cycles a stalled check may survive past its SIGKILL before we give \
+             up on killing it, mark the mountpoint wedged, and suppress further checks on it",
+        );
+
         ap.parse_args_or_exit();
     }
 
     let poll_interval_duration = Duration::from_secs(poll_interval);
+    let kill_grace_period_duration = Duration::from_secs(kill_grace_period);
 
     println!(
         "mount_status_monitor checking mounts every {} seconds",
         poll_interval_duration.as_secs()
     );
 
-    let syslog = syslog::unix(Facility::LOG_DAEMON).chain_err(|| "Unable to connect to syslog")?;
+    let syslog = Arc::new(
+        syslog::unix(Facility::LOG_DAEMON).chain_err(|| "Unable to connect to syslog")?,
+    );
 
-    let mut mount_statuses = HashMap::<PathBuf, MountStatus>::new();
+    let mount_statuses = Arc::new(Mutex::new(HashMap::<PathBuf, MountRecord>::new()));
+
+    // Where the kernel supports it, a background thread watches stalled
+    // checks via pidfd + epoll so SIGKILL escalation happens the instant a
+    // grace period expires rather than at the next --poll-interval tick.
+    // check_mounts' own try_wait-on-tick reaping is unchanged and remains
+    // the only path on kernels/platforms where this isn't available.
+    #[cfg(target_os = "linux")]
+    {
+        if reactor::pidfd_supported() {
+            let mount_statuses = Arc::clone(&mount_statuses);
+            let syslog = Arc::clone(&syslog);
+            thread::spawn(move || {
+                run_stall_reactor(&mount_statuses, &syslog, kill_grace_period_duration)
+            });
+        }
+    }
 
     loop {
-        check_mounts(&mut mount_statuses, &syslog);
+        {
+            let mut mount_statuses = mount_statuses.lock().unwrap();
+            check_mounts(
+                &mut mount_statuses,
+                &syslog,
+                kill_grace_period_duration,
+                force_unmount_after,
+                dead_after,
+                max_stalled_cycles,
+            );
+        }
 
         // We calculate these values each time because a filesystem may have been
         // mounted or unmounted since the last check:
-        let total_mounts = mount_statuses.len();
-        let dead_mounts = mount_statuses
+        let mount_statuses_guard = mount_statuses.lock().unwrap();
+        let total_mounts = mount_statuses_guard.len();
+        let dead_mounts = mount_statuses_guard
             .iter()
-            .filter(|&(_, status)| !status.success())
+            .filter(|&(_, record)| !record.status.success())
             .count();
+        let wedged_mounts = mount_statuses_guard
+            .iter()
+            .filter(|&(_, record)| record.wedged)
+            .count();
+        drop(mount_statuses_guard);
 
         // TODO: consider making this debug or sending it to stdout?
         syslog
             .info(format!(
-                "Checked {} mounts; {} are dead",
+                "Checked {} mounts; {} are dead ({} wedged)",
                 total_mounts,
-                dead_mounts
+                dead_mounts,
+                wedged_mounts
             ))
             .unwrap_or_else(handle_syslog_error);
 
         #[cfg(feature = "with_prometheus")]
         {
             if let Some(ref gateway_address) = prometheus_push_gateway {
-                if let Err(e) = push_to_prometheus(gateway_address, dead_mounts, total_mounts) {
+                let result =
+                    push_to_prometheus(gateway_address, dead_mounts, total_mounts, wedged_mounts);
+                if let Err(e) = result {
                     eprintln!("{}", e);
                 }
             }
@@ -169,24 +319,246 @@ fn real_main() -> Result<()> {
     }
 }
 
+/// Runs forever on a background thread, escalating stalled checks from
+/// SIGTERM to SIGKILL as soon as their grace period expires instead of
+/// waiting for the next --poll-interval tick. Actually reaping the exited
+/// process and spawning its replacement is still left to check_mounts.
+#[cfg(target_os = "linux")]
+fn run_stall_reactor(
+    mount_statuses: &Mutex<HashMap<PathBuf, MountRecord>>,
+    logger: &syslog::Logger,
+    kill_grace_period: Duration,
+) {
+    // One reactor for the life of this thread: opening/closing an
+    // epoll+timerfd pair every tick (including the common idle case of no
+    // stalled mounts) would mean a few fresh fds a second for the daemon's
+    // whole lifetime for no benefit. Mounts are added via watch() as they
+    // stall and removed via unwatch() once reaped or escalated, instead of
+    // rebuilding the whole set from scratch each pass.
+    let mut reactor = match StallReactor::new() {
+        Ok(reactor) => reactor,
+        Err(e) => {
+            eprintln!("Stall reactor unavailable, falling back to poll-interval reaping: {}", e);
+            return;
+        }
+    };
+
+    loop {
+        let mut next_deadline: Option<Duration> = None;
+        {
+            let mut statuses = mount_statuses.lock().unwrap();
+            let mut still_stalled: HashSet<PathBuf> = HashSet::new();
+            for (path, record) in statuses.iter_mut() {
+                if let MountStatus::CheckRunning {
+                    ref mut process,
+                    escalation,
+                    signaled_at,
+                    ..
+                } = record.status
+                {
+                    if escalation != KillEscalation::Sigtermed {
+                        continue;
+                    }
+
+                    // A readable pidfd just means the child exited; reap it
+                    // here with try_wait() so we never hand epoll a pidfd for
+                    // an already-terminated process. Otherwise that pidfd is
+                    // instantly readable and we'd spin this loop at 100% CPU
+                    // re-watching the same exited-but-unreaped child until
+                    // check_mounts' next poll tick finally reaps it.
+                    // check_mounts still does the "official" reap (logging,
+                    // metrics, spawning the replacement check) on its next
+                    // tick; calling try_wait() again there is safe since
+                    // std::process::Child caches the exit status once seen.
+                    match process.try_wait() {
+                        Ok(Some(_)) => continue,
+                        Ok(None) => {}
+                        Err(e) => {
+                            eprintln!(
+                                "Unable to poll stalled check for {}: {}",
+                                path.display(),
+                                e
+                            );
+                            continue;
+                        }
+                    }
+
+                    still_stalled.insert(path.clone());
+                    if !reactor.is_watching(path) {
+                        if let Err(e) = reactor.watch(path.clone(), process.id() as libc::pid_t) {
+                            eprintln!("Unable to watch pidfd for {}: {}", path.display(), e);
+                            continue;
+                        }
+                    }
+                    let remaining = kill_grace_period
+                        .checked_sub(signaled_at.elapsed())
+                        .unwrap_or_else(|| Duration::from_secs(0));
+                    next_deadline = Some(match next_deadline {
+                        Some(existing) if existing <= remaining => existing,
+                        _ => remaining,
+                    });
+                }
+            }
+
+            // Anything still registered with the reactor that isn't stalled
+            // any more (reaped, escalated to SIGKILL, or otherwise no longer
+            // CheckRunning+Sigtermed) needs to be dropped, or the watch set
+            // would grow without bound now that the reactor outlives a tick.
+            if let Err(e) = reactor.retain_watched(|path| still_stalled.contains(path)) {
+                eprintln!("Unable to unwatch a stalled-check pidfd: {}", e);
+            }
+        }
+
+        if reactor.is_empty() {
+            // Nothing stalled right now; avoid busy-looping until something is:
+            thread::sleep(Duration::from_millis(250));
+            continue;
+        }
+
+        if let Some(deadline) = next_deadline {
+            if let Err(e) = reactor.arm_deadline(deadline) {
+                eprintln!("Unable to arm stall-reactor deadline timer: {}", e);
+            }
+        }
+
+        // We don't need to act on individual events here: an Exited pidfd is
+        // reaped on our next pass through the scan-and-watch loop above (so
+        // it's never re-watched), and check_mounts' next tick still performs
+        // the official reap/respawn; a Deadline means we re-scan below for
+        // anything that needs SIGKILL.
+        if let Err(e) = reactor.wait() {
+            eprintln!("Stall reactor wait failed: {}", e);
+            thread::sleep(Duration::from_millis(250));
+            continue;
+        }
+
+        let mut statuses = mount_statuses.lock().unwrap();
+        for (path, record) in statuses.iter_mut() {
+            if let MountStatus::CheckRunning {
+                ref process,
+                ref mut escalation,
+                ref mut signaled_at,
+                ..
+            } = record.status
+            {
+                if *escalation == KillEscalation::Sigtermed && signaled_at.elapsed() >= kill_grace_period
+                {
+                    let pid = process.id() as libc::pid_t;
+                    if unsafe { libc::kill(pid, libc::SIGKILL) } != 0 {
+                        eprintln!(
+                            "Unable to send SIGKILL to process {}: {}",
+                            pid,
+                            std::io::Error::last_os_error()
+                        );
+                    }
+                    logger
+                        .warning(format!(
+                            "Stalled check on mount {} did not exit within its {} second grace \
+                             period; escalating from SIGTERM to SIGKILL",
+                            path.display(),
+                            kill_grace_period.as_secs()
+                        ))
+                        .unwrap_or_else(handle_syslog_error);
+                    *escalation = KillEscalation::Sigkilled;
+                    *signaled_at = Instant::now();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "with_prometheus")]
+lazy_static! {
+    static ref TOTAL_MOUNTS: prometheus::Gauge = register_gauge!(
+        "total_mountpoints",
+        "Total number of mountpoints"
+    ).unwrap();
+
+    static ref DEAD_MOUNTS: prometheus::Gauge = register_gauge!(
+        "dead_mountpoints",
+        "Number of unresponsive mountpoints"
+    ).unwrap();
+
+    static ref WEDGED_MOUNTS: prometheus::Gauge = register_gauge!(
+        "wedged_mountpoints",
+        "Number of mountpoints whose stalled check survived its SIGKILL retry budget and has \
+         been given up on"
+    ).unwrap();
+
+    static ref MOUNT_STATE: prometheus::GaugeVec = register_gauge_vec!(
+        "mount_state",
+        "Current health of a mountpoint (1 = passed last check, 0 = failed)",
+        &["mountpoint", "fstype"]
+    ).unwrap();
+
+    static ref CHECK_DURATION_SECONDS: prometheus::HistogramVec = register_histogram_vec!(
+        "check_duration_seconds",
+        "Wall-clock duration of each check_mount invocation",
+        &["mountpoint", "fstype"]
+    ).unwrap();
+
+    static ref FORCED_UNMOUNT_ATTEMPTS: prometheus::CounterVec = register_counter_vec!(
+        "forced_unmount_attempts_total",
+        "Number of forced unmount (MNT_FORCE) attempts on persistently dead mountpoints",
+        &["mountpoint", "result"]
+    ).unwrap();
+
+    static ref MOUNT_HEALTH_STATE: prometheus::GaugeVec = register_gauge_vec!(
+        "mount_health_state",
+        "Flap-aware mount health (0 = healthy, 1 = recovering, 2 = degraded, 3 = dead)",
+        &["mountpoint", "fstype"]
+    ).unwrap();
+
+    static ref MOUNT_CONSECUTIVE_FAILURES: prometheus::GaugeVec = register_gauge_vec!(
+        "mount_consecutive_failures",
+        "Number of consecutive failed checks for a mountpoint",
+        &["mountpoint", "fstype"]
+    ).unwrap();
+}
+
+// Small wrappers around the per-mountpoint vectors above so the
+// cfg(feature = "with_prometheus") call-sites in check_mounts stay readable:
+
+#[cfg(feature = "with_prometheus")]
+fn record_mount_state(mount_point: &Path, fstype: &str, healthy: bool) {
+    MOUNT_STATE
+        .with_label_values(&[&mount_point.to_string_lossy(), fstype])
+        .set(if healthy { 1.0 } else { 0.0 });
+}
+
+#[cfg(feature = "with_prometheus")]
+fn observe_check_duration(mount_point: &Path, fstype: &str, duration: Duration) {
+    let seconds = duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1e9;
+    CHECK_DURATION_SECONDS
+        .with_label_values(&[&mount_point.to_string_lossy(), fstype])
+        .observe(seconds);
+}
+
+#[cfg(feature = "with_prometheus")]
+fn record_mount_health(mount_point: &Path, fstype: &str, health: HealthState, consecutive_failures: u32) {
+    MOUNT_HEALTH_STATE
+        .with_label_values(&[&mount_point.to_string_lossy(), fstype])
+        .set(health.as_metric_value());
+    MOUNT_CONSECUTIVE_FAILURES
+        .with_label_values(&[&mount_point.to_string_lossy(), fstype])
+        .set(f64::from(consecutive_failures));
+}
+
+#[cfg(feature = "with_prometheus")]
+fn record_forced_unmount_attempt(mount_point: &Path, succeeded: bool) {
+    let result = if succeeded { "success" } else { "failure" };
+    FORCED_UNMOUNT_ATTEMPTS
+        .with_label_values(&[&mount_point.to_string_lossy(), result])
+        .inc();
+}
+
 #[cfg(feature = "with_prometheus")]
 fn push_to_prometheus(
     gateway: &str,
     dead_mounts: usize,
     total_mounts: usize,
+    wedged_mounts: usize,
 ) -> prometheus::Result<()> {
-    lazy_static! {
-        static ref TOTAL_MOUNTS: prometheus::Gauge = register_gauge!(
-            "total_mountpoints",
-            "Total number of mountpoints"
-        ).unwrap();
-
-        static ref DEAD_MOUNTS: prometheus::Gauge = register_gauge!(
-            "dead_mountpoints",
-            "Number of unresponsive mountpoints"
-        ).unwrap();
-    }
-
     let prometheus_instance = match hostname::get_hostname() {
         Some(hostname) => hostname,
         None => return Err(prometheus::Error::Msg("Unable to retrieve hostname".into())),
@@ -198,6 +570,7 @@ fn push_to_prometheus(
     // mountpoints:
     TOTAL_MOUNTS.set(total_mounts as f64);
     DEAD_MOUNTS.set(dead_mounts as f64);
+    WEDGED_MOUNTS.set(wedged_mounts as f64);
 
     prometheus::push_metrics(
         "mount_status_monitor",
@@ -207,7 +580,14 @@ fn push_to_prometheus(
     )
 }
 
-fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &syslog::Logger) {
+fn check_mounts(
+    mount_statuses: &mut HashMap<PathBuf, MountRecord>,
+    logger: &syslog::Logger,
+    kill_grace_period: Duration,
+    force_unmount_after: Option<u32>,
+    dead_after: u32,
+    max_stalled_cycles: u32,
+) {
     let mount_points = get_mounts::get_mount_points().unwrap_or_else(|err| {
         eprintln!("Failed to retrieve a list of mount-points: {:?}", err);
         std::process::exit(2);
@@ -215,22 +595,37 @@ fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &sys
 
     // Remove any mount status entries which are no longer in the current list of mountpoints:
     mount_statuses.retain(|ref k, _| {
-        mount_points.iter().position(|i| *i == **k).is_some()
+        mount_points.iter().position(|i| &i.path == *k).is_some()
     });
 
-    for mount_point in mount_points {
-        mount_statuses
-            .entry(mount_point)
-            .or_insert(MountStatus::Alive);
+    for mount_point in &mount_points {
+        let record = mount_statuses
+            .entry(mount_point.path.clone())
+            .or_insert_with(|| MountRecord {
+                fstype: mount_point.fstype.clone(),
+                status: MountStatus::Alive,
+                consecutive_failures: 0,
+                health: HealthState::Healthy,
+                health_changed_at: Instant::now(),
+                wedged: false,
+            });
+        // A mount may have been remounted with a different filesystem type
+        // since we last saw it, so keep the label fresh:
+        record.fstype = mount_point.fstype.clone();
     }
 
     mount_statuses
         .par_iter_mut()
-        .for_each(|(mount_point, mount_status)| {
+        .for_each(|(mount_point, record)| {
+            #[cfg(feature = "with_prometheus")]
+            let fstype = &record.fstype;
             if let MountStatus::CheckRunning {
                 ref mut process,
                 start_time,
-            } = *mount_status
+                ref mut escalation,
+                ref mut signaled_at,
+                ref mut stalled_cycles,
+            } = record.status
             {
                 match process.try_wait() {
                     Ok(Some(status)) => {
@@ -242,8 +637,81 @@ fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &sys
                                 start_time.elapsed().as_secs()
                             ))
                             .unwrap_or_else(handle_syslog_error);
+
+                        if record.wedged {
+                            logger
+                                .warning(format!(
+                                    "Previously wedged check for mount {} has finally reaped",
+                                    mount_point.display()
+                                ))
+                                .unwrap_or_else(handle_syslog_error);
+                            record.wedged = false;
+                        }
+
+                        #[cfg(feature = "with_prometheus")]
+                        observe_check_duration(mount_point, fstype, start_time.elapsed());
                     }
                     Ok(None) => {
+                        // A check that's still stalled hasn't failed any
+                        // differently than it did on the tick it first
+                        // stalled, but it needs to keep counting toward
+                        // Degraded/Dead on every tick it remains stalled --
+                        // otherwise a mount wedged for an hour reads
+                        // identically to one that failed exactly once and
+                        // never promotes past that first Degraded step.
+                        record.consecutive_failures =
+                            record.consecutive_failures.saturating_add(1);
+                        let next_health = record.health.advance(
+                            false,
+                            record.consecutive_failures,
+                            dead_after,
+                        );
+                        if next_health != record.health {
+                            logger
+                                .info(format!(
+                                    "Mount {} health transitioned from {:?} to {:?}",
+                                    mount_point.display(),
+                                    record.health,
+                                    next_health
+                                ))
+                                .unwrap_or_else(handle_syslog_error);
+                            record.health = next_health;
+                            record.health_changed_at = Instant::now();
+                        }
+                        #[cfg(feature = "with_prometheus")]
+                        record_mount_health(
+                            mount_point,
+                            fstype,
+                            record.health,
+                            record.consecutive_failures,
+                        );
+
+                        // A check stuck in CheckRunning never reaches the
+                        // force-unmount attempt at the end of this function
+                        // (it returns before getting there), so a stall has
+                        // to be able to trigger it too -- otherwise
+                        // --force-unmount-after only ever fires for mounts
+                        // that fail a quick check repeatedly, never for the
+                        // hung/wedged mount it's meant to rescue.
+                        // consecutive_failures now keeps advancing on every
+                        // stalled tick, so this still fires exactly once as
+                        // it crosses the threshold, same as the fall-through.
+                        if let Some(threshold) = force_unmount_after {
+                            maybe_force_unmount(
+                                mount_point,
+                                record.consecutive_failures,
+                                threshold,
+                                logger,
+                            );
+                        }
+
+                        if record.wedged {
+                            // Already given up on this one; don't repeat the
+                            // escalated log line or attempt another kill
+                            // every cycle, just keep waiting for it to reap.
+                            return;
+                        }
+
                         logger
                             .warning(format!(
                                 "Slow check for mount {} has not exited after {} seconds",
@@ -251,6 +719,44 @@ fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &sys
                                 start_time.elapsed().as_secs()
                             ))
                             .unwrap_or_else(handle_syslog_error);
+
+                        if *escalation == KillEscalation::Sigtermed
+                            && signaled_at.elapsed() >= kill_grace_period
+                        {
+                            let pid = process.id() as libc::pid_t;
+                            if unsafe { libc::kill(pid, libc::SIGKILL) } != 0 {
+                                eprintln!(
+                                    "Unable to send SIGKILL to process {}: {}",
+                                    pid,
+                                    std::io::Error::last_os_error()
+                                );
+                            }
+                            logger
+                                .warning(format!(
+                                    "Stalled check on mount {} did not exit within its {} second grace \
+                                     period; escalating from SIGTERM to SIGKILL",
+                                    mount_point.display(),
+                                    kill_grace_period.as_secs()
+                                ))
+                                .unwrap_or_else(handle_syslog_error);
+                            *escalation = KillEscalation::Sigkilled;
+                            *signaled_at = Instant::now();
+                        } else if *escalation == KillEscalation::Sigkilled {
+                            *stalled_cycles = stalled_cycles.saturating_add(1);
+                            if *stalled_cycles > max_stalled_cycles {
+                                logger
+                                    .err(format!(
+                                        "Stalled check on mount {} has survived {} poll cycles since \
+                                         its SIGKILL and is presumed permanently wedged; giving up on \
+                                         killing it and suppressing further checks on this mountpoint \
+                                         until it reaps",
+                                        mount_point.display(),
+                                        stalled_cycles
+                                    ))
+                                    .unwrap_or_else(handle_syslog_error);
+                                record.wedged = true;
+                            }
+                        }
                         return;
                     }
                     Err(e) => {
@@ -265,6 +771,8 @@ fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &sys
                     }
                 }
             }
+
+            let check_start = Instant::now();
             let new_mount_status = match check_mount(mount_point) {
                 Ok(status) => status,
                 Err(e) => {
@@ -272,6 +780,8 @@ fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &sys
                     return;
                 }
             };
+            #[cfg(feature = "with_prometheus")]
+            observe_check_duration(mount_point, fstype, check_start.elapsed());
 
             match new_mount_status {
                 MountStatus::CheckFailed(rc) => {
@@ -282,23 +792,117 @@ fn check_mounts(mount_statuses: &mut HashMap<PathBuf, MountStatus>, logger: &sys
                 }
                 _ => {}
             }
+            #[cfg(feature = "with_prometheus")]
+            record_mount_state(mount_point, fstype, new_mount_status.success());
+
             if new_mount_status.success() {
+                record.consecutive_failures = 0;
+            } else {
+                record.consecutive_failures = record.consecutive_failures.saturating_add(1);
+            }
+
+            let next_health = record.health.advance(
+                new_mount_status.success(),
+                record.consecutive_failures,
+                dead_after,
+            );
+            // Only log the pass/fail outcome when it actually changes the
+            // mount's health state; otherwise a long-Dead or long-Healthy
+            // mount would spam syslog every --poll-interval forever.
+            if next_health != record.health {
+                if new_mount_status.success() {
+                    logger
+                        .debug(format!(
+                            "Mount passed health-check: {}",
+                            mount_point.display()
+                        ))
+                        .unwrap_or_else(handle_syslog_error);
+                } else {
+                    let msg = format!("Mount failed health-check: {}", mount_point.display());
+                    eprintln!("{}", msg);
+                    logger.err(msg).unwrap_or_else(handle_syslog_error);
+                }
                 logger
-                    .debug(format!(
-                        "Mount passed health-check: {}",
-                        mount_point.display()
+                    .info(format!(
+                        "Mount {} health transitioned from {:?} to {:?}",
+                        mount_point.display(),
+                        record.health,
+                        next_health
                     ))
                     .unwrap_or_else(handle_syslog_error);
-            } else {
-                let msg = format!("Mount failed health-check: {}", mount_point.display());
-                eprintln!("{}", msg);
-                logger.err(msg).unwrap_or_else(handle_syslog_error);
+                record.health = next_health;
+                record.health_changed_at = Instant::now();
             }
 
-            *mount_status = new_mount_status;
+            #[cfg(feature = "with_prometheus")]
+            record_mount_health(mount_point, fstype, record.health, record.consecutive_failures);
+
+            if let Some(threshold) = force_unmount_after {
+                maybe_force_unmount(mount_point, record.consecutive_failures, threshold, logger);
+            }
+
+            record.status = new_mount_status;
         });
 }
 
+/// Attempts a forced unmount once `consecutive_failures` reaches `threshold`,
+/// logging and recording the outcome either way. Called from both the
+/// stalled-check path and the fall-through reap/respawn path in
+/// check_mounts so the two stay in sync.
+fn maybe_force_unmount(
+    mount_point: &Path,
+    consecutive_failures: u32,
+    threshold: u32,
+    logger: &syslog::Logger,
+) {
+    if consecutive_failures != threshold {
+        return;
+    }
+
+    logger
+        .warning(format!(
+            "Mount {} has failed {} consecutive checks; attempting a forced unmount",
+            mount_point.display(),
+            threshold
+        ))
+        .unwrap_or_else(handle_syslog_error);
+
+    match force_unmount(mount_point) {
+        Ok(()) => {
+            logger
+                .warning(format!("Forced unmount of {} succeeded", mount_point.display()))
+                .unwrap_or_else(handle_syslog_error);
+            #[cfg(feature = "with_prometheus")]
+            record_forced_unmount_attempt(mount_point, true);
+        }
+        Err(e) => {
+            logger
+                .err(format!("Forced unmount of {} failed: {}", mount_point.display(), e))
+                .unwrap_or_else(handle_syslog_error);
+            #[cfg(feature = "with_prometheus")]
+            record_forced_unmount_attempt(mount_point, false);
+        }
+    }
+}
+
+/// Attempt to detach a wedged mountpoint with `umount2(2)`'s `MNT_FORCE`
+/// flag. On Linux this aborts outstanding RPCs on network/FUSE filesystems,
+/// which can free processes stuck in uninterruptible sleep; if anything
+/// still holds the mount busy the call simply fails and we log it.
+fn force_unmount(mount_point: &Path) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(mount_point.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+    let rc = unsafe { libc::umount2(c_path.as_ptr(), libc::MNT_FORCE) };
+    if rc == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
 fn check_mount(mount_point: &Path) -> Result<MountStatus> {
     let start_time = Instant::now();
     let mut child = process::Command::new("/usr/bin/stat")
@@ -317,22 +921,31 @@ fn check_mount(mount_point: &Path) -> Result<MountStatus> {
                 The process has not exited and we're not going to wait for a
                 potentially very long period of time for it to recover.
 
-                We'll attempt to clean up the check process by killing it, which
-                is defined as sending SIGKILL on Unix:
-
-                https://doc.rust-lang.org/std/process/struct.Child.html#method.kill
+                Rather than SIGKILL-ing it immediately, we give it a chance to
+                unwind cleanly: send SIGTERM first and only escalate to
+                SIGKILL in check_mounts if it's still around after its grace
+                period. std::process::Child::kill() always sends SIGKILL, so
+                we go through libc directly here.
 
                 The mount_status structure returned will include this child
                 process instance so future checks can perform a non-blocking
                 test to see whether it has finally exited:
             */
-            if let Err(err) = child.kill() {
-                eprintln!("Unable to kill process {}: {:?}", child.id(), err)
-            };
+            let pid = child.id() as libc::pid_t;
+            if unsafe { libc::kill(pid, libc::SIGTERM) } != 0 {
+                eprintln!(
+                    "Unable to send SIGTERM to process {}: {}",
+                    pid,
+                    std::io::Error::last_os_error()
+                );
+            }
 
             Ok(MountStatus::CheckRunning {
                 process: child,
                 start_time: start_time,
+                escalation: KillEscalation::Sigtermed,
+                signaled_at: Instant::now(),
+                stalled_cycles: 0,
             })
         }
         Some(exit_status) => {